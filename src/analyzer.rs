@@ -0,0 +1,108 @@
+/*
+Copyright 2022-2025 czubix
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::lexer::{Token, TokenType};
+use crate::parser::AST;
+use std::collections::HashMap;
+
+/// Known names mapped to their arity, where known (`Some(n)`); `None` means
+/// the name exists but its arity isn't checked (builtins, host functions,
+/// modules, plain variables).
+type Names = HashMap<String, Option<usize>>;
+
+fn seed_locals(ast: &[AST], scope: &mut Names) {
+    for node in ast {
+        match node._type {
+            TokenType::Func => {
+                let arity = node.children.first().map(|params| params.children.len());
+                scope.insert(node.token.value.to_owned(), arity);
+            },
+            TokenType::Equal | TokenType::PlusEqual | TokenType::MinusEqual |
+            TokenType::MultiplyEqual | TokenType::DivideEqual | TokenType::ModuloEqual => {
+                scope.entry(node.token.value.to_owned()).or_insert(None);
+            },
+            _ => {}
+        }
+    }
+}
+
+fn check_node(node: &AST, scope: &Names, errors: &mut Vec<Token>) {
+    match node._type {
+        TokenType::Var if node.children.is_empty() => {
+            if !scope.contains_key(&node.token.value) {
+                errors.push(Token::new_error(TokenType::Undefined, format!("{} is not defined", node.token.value)).with_span(node.token.start, node.token.end));
+            }
+        },
+        TokenType::LeftParen => {
+            match scope.get(&node.token.value) {
+                None => errors.push(Token::new_error(TokenType::Undefined, format!("{} is not defined", node.token.value)).with_span(node.token.start, node.token.end)),
+                Some(Some(arity)) if *arity != node.children.len() => {
+                    errors.push(Token::new_error(TokenType::TypeError, format!("{} takes {} argument(s), got {}", node.token.value, arity, node.children.len())).with_span(node.token.start, node.token.end));
+                },
+                _ => {}
+            }
+        },
+        _ => {}
+    }
+}
+
+fn check_import(node: &AST, scope: &Names, errors: &mut Vec<Token>) {
+    for module in &node.children {
+        if !scope.contains_key(&module.token.value) {
+            errors.push(Token::new_error(TokenType::ModuleNotfound, format!("no module named {}", module.token.value)).with_span(module.token.start, module.token.end));
+        }
+    }
+}
+
+fn walk(ast: &[AST], scope: &Names, errors: &mut Vec<Token>) {
+    let mut scope = scope.to_owned();
+
+    seed_locals(ast, &mut scope);
+
+    for node in ast {
+        if node._type == TokenType::Import {
+            check_import(node, &scope, errors);
+            continue;
+        }
+
+        check_node(node, &scope, errors);
+
+        if node._type == TokenType::Func {
+            let mut func_scope = scope.clone();
+            let mut body: &[AST] = &[];
+
+            if let Some((params, rest)) = node.children.split_first() {
+                for param in &params.children {
+                    func_scope.insert(param.token.value.to_owned(), None);
+                }
+
+                body = rest;
+            }
+
+            walk(body, &func_scope, errors);
+        } else {
+            walk(&node.children, &scope, errors);
+        }
+    }
+}
+
+pub fn analyze(ast: &[AST], scope: Names) -> Vec<Token> {
+    let mut errors = Vec::new();
+
+    walk(ast, &scope, &mut errors);
+
+    errors
+}
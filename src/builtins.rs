@@ -17,7 +17,8 @@ limitations under the License.
 use crate::lexer::{Token, TokenType, RustObject};
 use crate::interpreter::{execute_ast, get_function, check_if_error, Function, Scope};
 use crate::utils::convert_to_token;
-use image::{ImageBuffer, Rgb, ImageResult, ImageFormat};
+use image::{ImageBuffer, Rgb, ImageResult, ImageFormat, ImageEncoder, ColorType};
+use image::codecs::jpeg::JpegEncoder;
 use std::io::Cursor;
 use rand::Rng;
 use pyo3::{prelude::*, types::PyDict};
@@ -41,6 +42,39 @@ impl Image {
         self.buffer.write_to(&mut Cursor::new(bytes), ImageFormat::Png)
     }
 
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let buffer = image::load_from_memory(bytes).map_err(|error| error.to_string())?.to_rgb8();
+
+        let (width, height) = buffer.dimensions();
+
+        if width < 128 || width > 1920 || height < 128 || height > 1920 {
+            return Err("width and height must be between 128 and 1920".to_string());
+        }
+
+        Ok(Self { buffer })
+    }
+
+    pub fn resize(&self, width: u32, height: u32) -> Self {
+        Self { buffer: image::imageops::resize(&self.buffer, width, height, image::imageops::FilterType::Lanczos3) }
+    }
+
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { buffer: image::imageops::crop_imm(&self.buffer, x, y, width, height).to_image() }
+    }
+
+    pub fn encode(&self, format: ImageFormat, quality: Option<u8>) -> ImageResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        if format == ImageFormat::Jpeg {
+            let encoder = JpegEncoder::new_with_quality(&mut bytes, quality.unwrap_or(80));
+            encoder.write_image(&self.buffer, self.buffer.width(), self.buffer.height(), ColorType::Rgb8)?;
+        } else {
+            self.buffer.write_to(&mut Cursor::new(&mut bytes), format)?;
+        }
+
+        Ok(bytes)
+    }
+
     pub fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 3]) {
         if x < self.buffer.width() && y < self.buffer.height() {
             self.buffer.put_pixel(x, y, Rgb(color));
@@ -82,6 +116,146 @@ impl Image {
             }
         }
     }
+
+    fn set_pixel_signed(&mut self, x: i32, y: i32, color: [u8; 3]) {
+        if x >= 0 && y >= 0 {
+            self.set_pixel(x as u32, y as u32, color);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: i64, y: i64, color: [u8; 3], alpha: f64) {
+        if x < 0 || y < 0 || x as u32 >= self.buffer.width() || y as u32 >= self.buffer.height() {
+            return;
+        }
+
+        let alpha = alpha.clamp(0.0, 1.0);
+        let existing = *self.buffer.get_pixel(x as u32, y as u32);
+
+        self.buffer.put_pixel(x as u32, y as u32, Rgb([
+            (color[0] as f64 * alpha + existing[0] as f64 * (1.0 - alpha)).round() as u8,
+            (color[1] as f64 * alpha + existing[1] as f64 * (1.0 - alpha)).round() as u8,
+            (color[2] as f64 * alpha + existing[2] as f64 * (1.0 - alpha)).round() as u8
+        ]));
+    }
+
+    pub fn draw_line_aa(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, color: [u8; 3]) {
+        let steep = (y2 - y1).abs() > (x2 - x1).abs();
+
+        let (mut x1, mut y1, mut x2, mut y2) = if steep { (y1, x1, y2, x2) } else { (x1, y1, x2, y2) };
+
+        if x1 > x2 {
+            std::mem::swap(&mut x1, &mut x2);
+            std::mem::swap(&mut y1, &mut y2);
+        }
+
+        let dx = x2 - x1;
+        let dy = y2 - y1;
+        let gradient = if dx == 0.0 { 1.0 } else { dy / dx };
+
+        let xstart = x1.round();
+        let xend = x2.round();
+        let mut intery = y1 + gradient * (xstart - x1);
+
+        // Endpoints fall mid-pixel in general, so Wu's algorithm scales their
+        // column by how far the x-coordinate sits from the pixel it rounded
+        // to - otherwise every line would render its first/last column at
+        // full coverage regardless of where it actually starts/ends.
+        let start_xgap = 1.0 - ((x1 + 0.5) - (x1 + 0.5).floor());
+        let end_xgap = (x2 + 0.5) - (x2 + 0.5).floor();
+
+        let mut x = xstart;
+
+        while x <= xend {
+            let xgap = if x == xstart {
+                start_xgap
+            } else if x == xend {
+                end_xgap
+            } else {
+                1.0
+            };
+
+            let (px, py) = if steep { (intery.floor(), x) } else { (x, intery.floor()) };
+            let (px2, py2) = if steep { (intery.floor() + 1.0, x) } else { (x, intery.floor() + 1.0) };
+
+            // `.fract()` keeps the sign of `intery`, so for negative y-values it
+            // returns a negative coverage instead of the distance to the pixel
+            // below - floor-based fract is what the rest of the loop assumes.
+            let coverage = intery - intery.floor();
+
+            self.blend_pixel(px as i64, py as i64, color, (1.0 - coverage) * xgap);
+            self.blend_pixel(px2 as i64, py2 as i64, color, coverage * xgap);
+
+            intery += gradient;
+            x += 1.0;
+        }
+    }
+
+    fn draw_hline(&mut self, x1: i32, x2: i32, y: i32, color: [u8; 3]) {
+        for x in x1..=x2 {
+            self.set_pixel_signed(x, y, color);
+        }
+    }
+
+    pub fn draw_circle(&mut self, xc: i32, yc: i32, r: i32, color: [u8; 3]) {
+        let mut x = 0;
+        let mut y = r;
+        let mut d = 3 - 2 * r;
+
+        while y >= x {
+            for &(px, py) in &[
+                (xc + x, yc + y), (xc - x, yc + y), (xc + x, yc - y), (xc - x, yc - y),
+                (xc + y, yc + x), (xc - y, yc + x), (xc + y, yc - x), (xc - y, yc - x)
+            ] {
+                self.set_pixel_signed(px, py, color);
+            }
+
+            if d < 0 {
+                d += 4 * x + 6;
+            } else {
+                d += 4 * (x - y) + 10;
+                y -= 1;
+            }
+
+            x += 1;
+        }
+    }
+
+    pub fn fill_circle(&mut self, xc: i32, yc: i32, r: i32, color: [u8; 3]) {
+        let mut x = 0;
+        let mut y = r;
+        let mut d = 3 - 2 * r;
+
+        while y >= x {
+            self.draw_hline(xc - x, xc + x, yc + y, color);
+            self.draw_hline(xc - x, xc + x, yc - y, color);
+            self.draw_hline(xc - y, xc + y, yc + x, color);
+            self.draw_hline(xc - y, xc + y, yc - x, color);
+
+            if d < 0 {
+                d += 4 * x + 6;
+            } else {
+                d += 4 * (x - y) + 10;
+                y -= 1;
+            }
+
+            x += 1;
+        }
+    }
+
+    pub fn draw_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        self.draw_line(x, y, x + width, y, color);
+        self.draw_line(x, y + height, x + width, y + height, color);
+        self.draw_line(x, y, x, y + height, color);
+        self.draw_line(x + width, y, x + width, y + height, color);
+    }
+
+    pub fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for py in y..=y + height {
+            for px in x..=x + width {
+                self.set_pixel(px, py, color);
+            }
+        }
+    }
 }
 
 macro_rules! check_args {
@@ -408,7 +582,8 @@ async fn map(name: String, args: Vec<Token>, scope: &mut Scope) -> Token {
             if let Some(body) = &function.body {
                 let mut function_scope = Scope {
                     variables: scope.variables.to_owned(),
-                    functions: scope.functions.to_owned()
+                    functions: scope.functions.to_owned(),
+                    fuel: scope.fuel.clone()
                 };
 
                 function_scope.push_variable(function.args[0].as_str(), arg);
@@ -427,6 +602,93 @@ async fn map(name: String, args: Vec<Token>, scope: &mut Scope) -> Token {
     Token::new_list(result_list)
 }
 
+async fn filter(name: String, args: Vec<Token>, scope: &mut Scope) -> Token {
+    check_args!(name, args, 2);
+
+    if args[0]._type != TokenType::List {
+        return Token::new_error(TokenType::TypeError, "filter() takes a list as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Str {
+        return Token::new_error(TokenType::TypeError, "filter() takes a string as its second argument".to_string());
+    }
+
+    let mut result_list: Vec<Token> = Vec::new();
+
+    for arg in args[0].list.to_owned() {
+        if let Some(function) = get_function(&args[1].value, &mut scope.to_owned()) {
+            if function.args.len() != 1 {
+                return Token::new_error(TokenType::TypeError, format!("function {} should take 1 argument", function.name));
+            }
+
+            if let Some(body) = &function.body {
+                let mut function_scope = Scope {
+                    variables: scope.variables.to_owned(),
+                    functions: scope.functions.to_owned(),
+                    fuel: scope.fuel.clone()
+                };
+
+                function_scope.push_variable(function.args[0].as_str(), arg.to_owned());
+
+                let result = execute_ast(body.to_owned(), &mut function_scope, Some(Token::new(TokenType::Func)), 0).await;
+
+                if check_if_error(&result) {
+                    return result;
+                }
+
+                if result._type == TokenType::Bool && result.number != 0.0 {
+                    result_list.push(arg);
+                }
+            }
+        }
+    }
+
+    Token::new_list(result_list)
+}
+
+async fn reduce(name: String, args: Vec<Token>, scope: &mut Scope) -> Token {
+    check_args!(name, args, 3);
+
+    if args[0]._type != TokenType::List {
+        return Token::new_error(TokenType::TypeError, "reduce() takes a list as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Str {
+        return Token::new_error(TokenType::TypeError, "reduce() takes a string as its second argument".to_string());
+    }
+
+    let mut accumulator = args[2].to_owned();
+
+    for arg in args[0].list.to_owned() {
+        if let Some(function) = get_function(&args[1].value, &mut scope.to_owned()) {
+            if function.args.len() != 2 {
+                return Token::new_error(TokenType::TypeError, format!("function {} should take 2 arguments", function.name));
+            }
+
+            if let Some(body) = &function.body {
+                let mut function_scope = Scope {
+                    variables: scope.variables.to_owned(),
+                    functions: scope.functions.to_owned(),
+                    fuel: scope.fuel.clone()
+                };
+
+                function_scope.push_variable(function.args[0].as_str(), accumulator.to_owned());
+                function_scope.push_variable(function.args[1].as_str(), arg);
+
+                let result = execute_ast(body.to_owned(), &mut function_scope, Some(Token::new(TokenType::Func)), 0).await;
+
+                if check_if_error(&result) {
+                    return result;
+                }
+
+                accumulator = result;
+            }
+        }
+    }
+
+    accumulator
+}
+
 async fn _await(name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
     check_args!(name, args);
 
@@ -497,13 +759,119 @@ async fn _image(name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
     Token::new_rustobject(RustObject::Image(image.unwrap()))
 }
 
+async fn image_from_bytes(name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args);
+
+    if args[0]._type != TokenType::Bytes {
+        return Token::new_error(TokenType::Error, "ImageFromBytes() takes bytes as its first argument".to_string());
+    }
+
+    match Image::from_bytes(&args[0].bytes) {
+        Ok(image) => Token::new_rustobject(RustObject::Image(image)),
+        Err(error) => Token::new_error(TokenType::Error, error)
+    }
+}
+
+async fn image_resize(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 2);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.resize() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.resize() takes an int as its second argument".to_string());
+    }
+
+    let width = args[0].number as u32;
+    let height = args[1].number as u32;
+
+    if !(128..=1920).contains(&width) || !(128..=1920).contains(&height) {
+        return Token::new_error(TokenType::Error, "width and height must be between 128 and 1920".to_string());
+    }
+
+    let RustObject::Image(image) = object.rustobject.unwrap() else { unreachable!() };
+
+    Token::new_rustobject(RustObject::Image(image.resize(width, height)))
+}
+
+async fn image_crop(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 4);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.crop() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.crop() takes an int as its second argument".to_string());
+    }
+
+    if args[2]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.crop() takes an int as its third argument".to_string());
+    }
+
+    if args[3]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.crop() takes an int as its fourth argument".to_string());
+    }
+
+    let x = args[0].number as u32;
+    let y = args[1].number as u32;
+    let width = args[2].number as u32;
+    let height = args[3].number as u32;
+
+    if !(128..=1920).contains(&width) || !(128..=1920).contains(&height) {
+        return Token::new_error(TokenType::Error, "width and height must be between 128 and 1920".to_string());
+    }
+
+    let RustObject::Image(image) = object.rustobject.unwrap() else { unreachable!() };
+
+    // `crop_imm` silently clamps an out-of-bounds rectangle to what fits in the
+    // source buffer, so check against the actual dimensions here - otherwise a
+    // request that passes the 128-1920 check above could still yield a cropped
+    // image smaller than the invariant this fix is supposed to enforce.
+    if x >= image.buffer.width() || y >= image.buffer.height() || x + width > image.buffer.width() || y + height > image.buffer.height() {
+        return Token::new_error(TokenType::Error, "crop area is out of bounds".to_string());
+    }
+
+    Token::new_rustobject(RustObject::Image(image.crop(x, y, width, height)))
+}
+
 async fn image_get_data(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
-    let RustObject::Image(mut image) = object.rustobject.unwrap() else { unreachable!() };
+    check_args!(name, args, 0, 2);
+
+    let format = if !args.is_empty() {
+        if args[0]._type != TokenType::Str {
+            return Token::new_error(TokenType::TypeError, "Image.get_data() takes a string as its first argument".to_string());
+        }
 
-    let mut bytes = Vec::new();
-    image.write_to(&mut bytes).unwrap();
+        match args[0].value.to_lowercase().as_str() {
+            "png" => ImageFormat::Png,
+            "jpeg" | "jpg" => ImageFormat::Jpeg,
+            "webp" => ImageFormat::WebP,
+            "bmp" => ImageFormat::Bmp,
+            "gif" => ImageFormat::Gif,
+            _ => return Token::new_error(TokenType::TypeError, format!("unknown image format: {}", args[0].value))
+        }
+    } else {
+        ImageFormat::Png
+    };
 
-    Token::new_bytes(bytes)
+    let quality = if args.len() >= 2 {
+        if args[1]._type != TokenType::Int {
+            return Token::new_error(TokenType::TypeError, "Image.get_data() takes an int as its second argument".to_string());
+        }
+
+        Some(args[1].number as u8)
+    } else {
+        None
+    };
+
+    let RustObject::Image(image) = object.rustobject.unwrap() else { unreachable!() };
+
+    match image.encode(format, quality) {
+        Ok(bytes) => Token::new_bytes(bytes),
+        Err(error) => Token::new_error(TokenType::Error, error.to_string())
+    }
 }
 
 async fn image_set_pixel(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
@@ -568,6 +936,172 @@ async fn image_draw_line(object: Token, name: String, args: Vec<Token>, _scope:
     Token::new_rustobject(RustObject::Image(image))
 }
 
+async fn image_draw_line_aa(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 5);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_line_aa() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_line_aa() takes an int as its second argument".to_string());
+    }
+
+    if args[2]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_line_aa() takes an int as its third argument".to_string());
+    }
+
+    if args[3]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_line_aa() takes an int as its fourth argument".to_string());
+    }
+
+    if args[4]._type != TokenType::List {
+        return Token::new_error(TokenType::Error, "Image.draw_line_aa() takes a list of ints as fifth argument".to_string());
+    }
+
+    let RustObject::Image(mut image) = object.rustobject.unwrap() else { unreachable!() };
+
+    let color = [args[4].list[0].number as u8, args[4].list[1].number as u8, args[4].list[2].number as u8];
+
+    image.draw_line_aa(args[0].number, args[1].number, args[2].number, args[3].number, color);
+
+    Token::new_rustobject(RustObject::Image(image))
+}
+
+async fn image_draw_circle(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 4);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_circle() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_circle() takes an int as its second argument".to_string());
+    }
+
+    if args[2]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_circle() takes an int as its third argument".to_string());
+    }
+
+    if args[3]._type != TokenType::List {
+        return Token::new_error(TokenType::Error, "Image.draw_circle() takes a list of ints as its fourth argument".to_string());
+    }
+
+    let RustObject::Image(mut image) = object.rustobject.unwrap() else { unreachable!() };
+
+    let xc = args[0].number as i32;
+    let yc = args[1].number as i32;
+    let r = args[2].number as i32;
+    let color = [args[3].list[0].number as u8, args[3].list[1].number as u8, args[3].list[2].number as u8];
+
+    image.draw_circle(xc, yc, r, color);
+
+    Token::new_rustobject(RustObject::Image(image))
+}
+
+async fn image_fill_circle(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 4);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_circle() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_circle() takes an int as its second argument".to_string());
+    }
+
+    if args[2]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_circle() takes an int as its third argument".to_string());
+    }
+
+    if args[3]._type != TokenType::List {
+        return Token::new_error(TokenType::Error, "Image.fill_circle() takes a list of ints as its fourth argument".to_string());
+    }
+
+    let RustObject::Image(mut image) = object.rustobject.unwrap() else { unreachable!() };
+
+    let xc = args[0].number as i32;
+    let yc = args[1].number as i32;
+    let r = args[2].number as i32;
+    let color = [args[3].list[0].number as u8, args[3].list[1].number as u8, args[3].list[2].number as u8];
+
+    image.fill_circle(xc, yc, r, color);
+
+    Token::new_rustobject(RustObject::Image(image))
+}
+
+async fn image_draw_rect(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 5);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_rect() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_rect() takes an int as its second argument".to_string());
+    }
+
+    if args[2]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_rect() takes an int as its third argument".to_string());
+    }
+
+    if args[3]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.draw_rect() takes an int as its fourth argument".to_string());
+    }
+
+    if args[4]._type != TokenType::List {
+        return Token::new_error(TokenType::Error, "Image.draw_rect() takes a list of ints as fifth argument".to_string());
+    }
+
+    let RustObject::Image(mut image) = object.rustobject.unwrap() else { unreachable!() };
+
+    let x = args[0].number as u32;
+    let y = args[1].number as u32;
+    let width = args[2].number as u32;
+    let height = args[3].number as u32;
+    let color = [args[4].list[0].number as u8, args[4].list[1].number as u8, args[4].list[2].number as u8];
+
+    image.draw_rect(x, y, width, height, color);
+
+    Token::new_rustobject(RustObject::Image(image))
+}
+
+async fn image_fill_rect(object: Token, name: String, args: Vec<Token>, _scope: &mut Scope) -> Token {
+    check_args!(name, args, 5);
+
+    if args[0]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_rect() takes an int as its first argument".to_string());
+    }
+
+    if args[1]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_rect() takes an int as its second argument".to_string());
+    }
+
+    if args[2]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_rect() takes an int as its third argument".to_string());
+    }
+
+    if args[3]._type != TokenType::Int {
+        return Token::new_error(TokenType::Error, "Image.fill_rect() takes an int as its fourth argument".to_string());
+    }
+
+    if args[4]._type != TokenType::List {
+        return Token::new_error(TokenType::Error, "Image.fill_rect() takes a list of ints as fifth argument".to_string());
+    }
+
+    let RustObject::Image(mut image) = object.rustobject.unwrap() else { unreachable!() };
+
+    let x = args[0].number as u32;
+    let y = args[1].number as u32;
+    let width = args[2].number as u32;
+    let height = args[3].number as u32;
+    let color = [args[4].list[0].number as u8, args[4].list[1].number as u8, args[4].list[2].number as u8];
+
+    image.fill_rect(x, y, width, height, color);
+
+    Token::new_rustobject(RustObject::Image(image))
+}
+
 pub fn get_builtins() -> Vec<Function> {
     vec![
         Function::new_builtin("get"),
@@ -583,9 +1117,12 @@ pub fn get_builtins() -> Vec<Function> {
         Function::new_builtin("str"),
         Function::new_builtin("int"),
         Function::new_builtin("map"),
+        Function::new_builtin("filter"),
+        Function::new_builtin("reduce"),
         Function::new_builtin("await"),
         Function::new_builtin("Error"),
-        Function::new_builtin("Image")
+        Function::new_builtin("Image"),
+        Function::new_builtin("ImageFromBytes")
     ]
 }
 
@@ -619,9 +1156,12 @@ pub async fn call_builtin(name: String, args: Vec<Token>, scope: &mut Scope) ->
     wrap!(_str, "str");
     wrap!(_int, "int");
     wrap!(map, "map");
+    wrap!(filter, "filter");
+    wrap!(reduce, "reduce");
     wrap!(_await, "await");
     wrap!(error, "Error");
     wrap!(_image, "Image");
+    wrap!(image_from_bytes, "ImageFromBytes");
 
     None
 }
@@ -649,6 +1189,13 @@ pub async fn call_method(object: Token, name: String, args: Vec<Token>, scope: &
     wrap!(image_get_data, "Image_get_data");
     wrap!(image_set_pixel, "Image_set_pixel");
     wrap!(image_draw_line, "Image_draw_line");
+    wrap!(image_draw_line_aa, "Image_draw_line_aa");
+    wrap!(image_draw_circle, "Image_draw_circle");
+    wrap!(image_fill_circle, "Image_fill_circle");
+    wrap!(image_draw_rect, "Image_draw_rect");
+    wrap!(image_fill_rect, "Image_fill_rect");
+    wrap!(image_resize, "Image_resize");
+    wrap!(image_crop, "Image_crop");
 
     None
 }
\ No newline at end of file
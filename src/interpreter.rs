@@ -0,0 +1,467 @@
+/*
+Copyright 2022-2025 czubix
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::lexer::{Token, TokenType};
+use crate::parser::AST;
+use crate::{builtins, utils};
+use pyo3::{prelude::*, types::PyTuple};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+const MAX_DEPTH: u64 = 256;
+
+/// A cooperative step budget shared between a script's top-level run and every
+/// nested call it spawns (map/filter/reduce callbacks, recursive calls). `Arc`
+/// rather than `Rc` because the future this lives in is driven by the tokio
+/// multi-thread runtime via `pyo3_asyncio::tokio::future_into_py`.
+#[derive(Clone, Debug)]
+pub struct Fuel {
+    remaining: Arc<AtomicU64>,
+    unlimited: bool
+}
+
+impl Fuel {
+    pub fn new(max_steps: Option<u64>) -> Self {
+        match max_steps {
+            Some(steps) => Self { remaining: Arc::new(AtomicU64::new(steps)), unlimited: false },
+            None => Self { remaining: Arc::new(AtomicU64::new(0)), unlimited: true }
+        }
+    }
+
+    pub fn tick(&self) -> bool {
+        if self.unlimited {
+            return true;
+        }
+
+        let mut current = self.remaining.load(Ordering::Relaxed);
+
+        loop {
+            if current == 0 {
+                return false;
+            }
+
+            match self.remaining.compare_exchange_weak(current, current - 1, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return true,
+                Err(actual) => current = actual
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Variable {
+    pub name: String,
+    pub value: Token
+}
+
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub name: String,
+    pub args: Vec<String>,
+    pub body: Option<Vec<AST>>,
+    pub is_builtin: bool,
+    pub pyfunc: Option<PyObject>
+}
+
+impl Function {
+    pub fn new_builtin(name: &str) -> Self {
+        Self { name: name.to_string(), args: Vec::new(), body: None, is_builtin: true, pyfunc: None }
+    }
+
+    pub fn new(name: String, args: Vec<String>, body: Vec<AST>) -> Self {
+        Self { name, args, body: Some(body), is_builtin: false, pyfunc: None }
+    }
+
+    pub fn new_pyfunc(name: String, pyfunc: PyObject) -> Self {
+        Self { name, args: Vec::new(), body: None, is_builtin: false, pyfunc: Some(pyfunc) }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Scope {
+    pub variables: Vec<Variable>,
+    pub functions: Vec<Function>,
+    pub fuel: Fuel
+}
+
+impl Scope {
+    pub fn new() -> Self {
+        Self { variables: Vec::new(), functions: Vec::new(), fuel: Fuel::new(None) }
+    }
+
+    pub fn push_variable(&mut self, name: &str, value: Token) {
+        self.variables.retain(|variable| variable.name != name);
+        self.variables.push(Variable { name: name.to_string(), value });
+    }
+
+    pub fn push_pyfunc(&mut self, name: &str, pyfunc: PyObject) {
+        self.functions.retain(|function| function.name != name);
+        self.functions.push(Function::new_pyfunc(name.to_string(), pyfunc));
+    }
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn get_function(name: &str, scope: &mut Scope) -> Option<Function> {
+    scope.functions.iter().find(|function| function.name == name).cloned()
+}
+
+pub fn check_if_error(token: &Token) -> bool {
+    matches!(token._type,
+        TokenType::Error | TokenType::Undefined | TokenType::RecursionError |
+        TokenType::SyntaxError | TokenType::TypeError | TokenType::IndexError |
+        TokenType::Unsupported | TokenType::ModuleNotfound | TokenType::StepLimitExceeded
+    )
+}
+
+fn truthy(token: &Token) -> bool {
+    match token._type {
+        TokenType::Int | TokenType::Bool => token.number != 0.0,
+        TokenType::Str => !token.value.is_empty(),
+        TokenType::List => !token.list.is_empty(),
+        TokenType::None => false,
+        _ => true
+    }
+}
+
+enum Flow {
+    Normal(Token),
+    Return(Token)
+}
+
+fn unwrap_flow(flow: Flow) -> Token {
+    match flow {
+        Flow::Normal(value) | Flow::Return(value) => value
+    }
+}
+
+fn exec_block(ast: Vec<AST>, scope: &mut Scope, depth: u64) -> Pin<Box<dyn Future<Output = Flow> + Send + '_>> {
+    Box::pin(async move {
+        if depth > MAX_DEPTH {
+            return Flow::Normal(Token::new_error(TokenType::RecursionError, "maximum recursion depth exceeded".to_string()));
+        }
+
+        let mut result = Token::new_none();
+
+        for node in ast {
+            if !scope.fuel.tick() {
+                return Flow::Normal(Token::new_error(TokenType::StepLimitExceeded, "step limit exceeded".to_string()));
+            }
+
+            tokio::task::yield_now().await;
+
+            if node._type == TokenType::Return {
+                let value = match node.children.into_iter().next() {
+                    Some(expr) => eval(expr, scope, depth).await,
+                    None => Token::new_none()
+                };
+
+                return Flow::Return(value);
+            }
+
+            if node._type == TokenType::If {
+                match exec_if(node, scope, depth).await {
+                    Flow::Return(value) => return Flow::Return(value),
+                    Flow::Normal(value) => result = value
+                }
+
+                continue;
+            }
+
+            result = eval(node, scope, depth).await;
+
+            if check_if_error(&result) {
+                return Flow::Normal(result);
+            }
+        }
+
+        Flow::Normal(result)
+    })
+}
+
+async fn exec_if(node: AST, scope: &mut Scope, depth: u64) -> Flow {
+    let mut children = node.children.into_iter();
+
+    let cond_node = match children.next() {
+        Some(cond_node) => cond_node,
+        None => return Flow::Normal(Token::new_error(TokenType::SyntaxError, "if is missing a condition".to_string()))
+    };
+
+    let then_node = match children.next() {
+        Some(then_node) => then_node,
+        None => return Flow::Normal(Token::new_error(TokenType::SyntaxError, "if is missing a body".to_string()))
+    };
+
+    let else_node = children.next();
+
+    let cond = eval(cond_node, scope, depth).await;
+
+    if check_if_error(&cond) {
+        return Flow::Normal(cond);
+    }
+
+    if truthy(&cond) {
+        exec_block(then_node.children, scope, depth + 1).await
+    } else if let Some(else_node) = else_node {
+        exec_block(else_node.children, scope, depth + 1).await
+    } else {
+        Flow::Normal(Token::new_none())
+    }
+}
+
+async fn call_pyfunc(pyfunc: &PyObject, args: Vec<Token>) -> Token {
+    Python::with_gil(|py| {
+        let py_args = PyTuple::new(py, args.into_iter().map(|arg| utils::to_pyobject(py, arg)).collect::<Vec<_>>());
+
+        match pyfunc.call1(py, py_args) {
+            Ok(result) => utils::to_token(py, result),
+            Err(error) => Token::new_error(TokenType::Error, error.to_string())
+        }
+    })
+}
+
+async fn call_function(name: &str, args: Vec<Token>, scope: &mut Scope, depth: u64) -> Token {
+    if let Some(result) = builtins::call_builtin(name.to_string(), args.clone(), scope).await {
+        return result;
+    }
+
+    let function = match get_function(name, scope) {
+        Some(function) => function,
+        None => return Token::new_error(TokenType::Undefined, format!("{name} is not defined"))
+    };
+
+    if let Some(pyfunc) = &function.pyfunc {
+        return call_pyfunc(pyfunc, args).await;
+    }
+
+    let body = match &function.body {
+        Some(body) => body,
+        None => return Token::new_error(TokenType::Undefined, format!("{name} is not defined"))
+    };
+
+    if function.args.len() != args.len() {
+        return Token::new_error(TokenType::TypeError, format!("function {} takes {} argument(s)", function.name, function.args.len()));
+    }
+
+    let mut function_scope = Scope {
+        variables: scope.variables.to_owned(),
+        functions: scope.functions.to_owned(),
+        fuel: scope.fuel.clone()
+    };
+
+    for (param, arg) in function.args.iter().zip(args) {
+        function_scope.push_variable(param, arg);
+    }
+
+    execute_ast(body.to_owned(), &mut function_scope, Some(Token::new(TokenType::Func)), depth + 1).await
+}
+
+fn eval(node: AST, scope: &mut Scope, depth: u64) -> Pin<Box<dyn Future<Output = Token> + Send + '_>> {
+    Box::pin(async move {
+        if !scope.fuel.tick() {
+            return Token::new_error(TokenType::StepLimitExceeded, "step limit exceeded".to_string());
+        }
+
+        match node._type {
+            TokenType::Int | TokenType::Str | TokenType::Bool | TokenType::None | TokenType::Bytes => node.token,
+            TokenType::List => {
+                let mut items = Vec::new();
+
+                for child in node.children {
+                    let value = eval(child, scope, depth).await;
+
+                    if check_if_error(&value) {
+                        return value;
+                    }
+
+                    items.push(value);
+                }
+
+                Token::new_list(items)
+            },
+            TokenType::Var if node.children.is_empty() => {
+                match scope.variables.iter().find(|variable| variable.name == node.token.value) {
+                    Some(variable) => variable.value.to_owned(),
+                    None => Token::new_error(TokenType::Undefined, format!("{} is not defined", node.token.value)).with_span(node.token.start, node.token.end)
+                }
+            },
+            TokenType::LeftParen => {
+                let mut args = Vec::new();
+
+                for child in node.children {
+                    let value = eval(child, scope, depth).await;
+
+                    if check_if_error(&value) {
+                        return value;
+                    }
+
+                    args.push(value);
+                }
+
+                call_function(&node.token.value, args, scope, depth + 1).await
+            },
+            TokenType::Dot => {
+                let mut children = node.children.into_iter();
+
+                let object_node = match children.next() {
+                    Some(object_node) => object_node,
+                    None => return Token::new_error(TokenType::SyntaxError, "method call is missing a receiver".to_string())
+                };
+
+                let object = eval(object_node, scope, depth).await;
+
+                if check_if_error(&object) {
+                    return object;
+                }
+
+                let mut args = Vec::new();
+
+                for child in children {
+                    let value = eval(child, scope, depth).await;
+
+                    if check_if_error(&value) {
+                        return value;
+                    }
+
+                    args.push(value);
+                }
+
+                match builtins::call_method(object, node.token.value.clone(), args, scope).await {
+                    Some(result) => result,
+                    None => Token::new_error(TokenType::TypeError, format!("no method named {}", node.token.value))
+                }
+            },
+            TokenType::Equal | TokenType::PlusEqual | TokenType::MinusEqual |
+            TokenType::MultiplyEqual | TokenType::DivideEqual | TokenType::ModuloEqual => {
+                let child = match node.children.into_iter().next() {
+                    Some(child) => child,
+                    None => return Token::new_error(TokenType::SyntaxError, "assignment is missing a value".to_string())
+                };
+
+                let value = eval(child, scope, depth).await;
+
+                if check_if_error(&value) {
+                    return value;
+                }
+
+                let value = if node._type == TokenType::Equal {
+                    value
+                } else {
+                    let current = match scope.variables.iter().find(|variable| variable.name == node.token.value) {
+                        Some(variable) => variable.value.to_owned(),
+                        None => return Token::new_error(TokenType::Undefined, format!("{} is not defined", node.token.value)).with_span(node.token.start, node.token.end)
+                    };
+
+                    match node._type {
+                        TokenType::PlusEqual => current + value,
+                        TokenType::MinusEqual => current - value,
+                        TokenType::MultiplyEqual => current * value,
+                        TokenType::DivideEqual => current / value,
+                        TokenType::ModuloEqual => current % value,
+                        _ => unreachable!()
+                    }
+                };
+
+                if check_if_error(&value) {
+                    return value;
+                }
+
+                scope.push_variable(&node.token.value, value.to_owned());
+
+                value
+            },
+            TokenType::Plus | TokenType::Minus | TokenType::Multiply | TokenType::Divide | TokenType::Modulo |
+            TokenType::EqualTo | TokenType::NotEqual | TokenType::Greater | TokenType::Less |
+            TokenType::GreaterEqual | TokenType::LessEqual | TokenType::And | TokenType::Or => {
+                let mut children = node.children.into_iter();
+
+                let left = match children.next() {
+                    Some(left) => eval(left, scope, depth).await,
+                    None => return Token::new_error(TokenType::SyntaxError, "operator is missing its left operand".to_string())
+                };
+
+                if check_if_error(&left) {
+                    return left;
+                }
+
+                let right = match children.next() {
+                    Some(right) => eval(right, scope, depth).await,
+                    None => return Token::new_error(TokenType::SyntaxError, "operator is missing its right operand".to_string())
+                };
+
+                if check_if_error(&right) {
+                    return right;
+                }
+
+                match node._type {
+                    TokenType::Plus => left + right,
+                    TokenType::Minus => left - right,
+                    TokenType::Multiply => left * right,
+                    TokenType::Divide => left / right,
+                    TokenType::Modulo => left % right,
+                    TokenType::EqualTo => left.eq(right),
+                    TokenType::NotEqual => left.ne(right),
+                    TokenType::Greater => left.gt(right),
+                    TokenType::Less => left.lt(right),
+                    TokenType::GreaterEqual => left.ge(right),
+                    TokenType::LessEqual => left.le(right),
+                    TokenType::And => left.and(right),
+                    TokenType::Or => left.or(right),
+                    _ => unreachable!()
+                }
+            },
+            TokenType::Not => {
+                let child = match node.children.into_iter().next() {
+                    Some(child) => child,
+                    None => return Token::new_error(TokenType::SyntaxError, "! is missing its operand".to_string())
+                };
+
+                let value = eval(child, scope, depth).await;
+
+                if check_if_error(&value) {
+                    return value;
+                }
+
+                value.not()
+            },
+            TokenType::Func => {
+                let mut children = node.children.into_iter();
+
+                let params = children.next().map(|params| params.children).unwrap_or_default();
+                let params = params.into_iter().map(|param| param.token.value).collect::<Vec<String>>();
+                let body = children.collect::<Vec<AST>>();
+
+                scope.functions.retain(|function| function.name != node.token.value);
+                scope.functions.push(Function::new(node.token.value.clone(), params, body));
+
+                Token::new_none()
+            },
+            TokenType::Import => Token::new_none(),
+            TokenType::Scope => unwrap_flow(exec_block(node.children, scope, depth + 1).await),
+            _ => Token::new_error(TokenType::Unsupported, format!("{:?} is not a supported expression", node._type))
+        }
+    })
+}
+
+pub fn execute_ast(ast: Vec<AST>, scope: &mut Scope, _context: Option<Token>, depth: u64) -> Pin<Box<dyn Future<Output = Token> + Send + '_>> {
+    Box::pin(async move { unwrap_flow(exec_block(ast, scope, depth).await) })
+}
@@ -14,17 +14,18 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::str::{Chars, FromStr};
+use std::str::{CharIndices, FromStr};
 use std::fmt::Display;
 use std::ops::{Add, Sub, Mul, Div, Rem};
 use std::cmp::{PartialEq, PartialOrd};
 use std::{iter::Peekable, collections::HashMap};
 use pyo3::{prelude::Py, types::PyAny};
+use serde::{Serialize, Deserialize};
 use crate::interpreter::Scope;
 use crate::builtins::Image as ImageStruct;
 
 #[allow(dead_code)]
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum TokenType {
     Unknown,
 
@@ -47,7 +48,7 @@ pub enum TokenType {
     List, Scope,
     PyObject, RustObject,
 
-    Error, Undefined, RecursionError, SyntaxError, TypeError, IndexError, Unsupported, ModuleNotfound
+    Error, Undefined, RecursionError, SyntaxError, TypeError, IndexError, Unsupported, ModuleNotfound, StepLimitExceeded
 }
 
 #[allow(dead_code)]
@@ -88,7 +89,7 @@ impl FromStr for TokenType {
             List, Scope,
             PyObject, RustObject,
 
-            Error, Undefined, RecursionError, SyntaxError, TypeError, IndexError, Unsupported, ModuleNotfound
+            Error, Undefined, RecursionError, SyntaxError, TypeError, IndexError, Unsupported, ModuleNotfound, StepLimitExceeded
         )
     }
 }
@@ -98,16 +99,21 @@ pub enum RustObject {
     Image(ImageStruct)
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Token {
     pub _type: TokenType,
     pub value: String,
     pub number: f64,
     pub list: Vec<Token>,
     pub bytes: Vec<u8>,
+    #[serde(skip)]
     pub scope: Option<Scope>,
+    #[serde(skip)]
     pub pyobject: Option<Py<PyAny>>,
+    #[serde(skip)]
     pub rustobject: Option<RustObject>,
+    pub start: usize,
+    pub end: usize,
 }
 
 fn unsupported_operand(operator: &str, _self: Token, other: Token) -> Token {
@@ -284,7 +290,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -361,7 +369,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -374,7 +384,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -387,7 +399,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -400,7 +414,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -413,7 +429,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -430,7 +448,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -443,7 +463,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -456,7 +478,9 @@ impl Token {
             bytes,
             scope: None,
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -469,7 +493,9 @@ impl Token {
             bytes: Vec::new(),
             scope: Some(scope),
             pyobject: None,
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -482,7 +508,9 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: Some(pyobject),
-            rustobject: None
+            rustobject: None,
+            start: 0,
+            end: 0
         }
     }
 
@@ -495,9 +523,23 @@ impl Token {
             bytes: Vec::new(),
             scope: None,
             pyobject: None,
-            rustobject: Some(rustobject)
+            rustobject: Some(rustobject),
+            start: 0,
+            end: 0
         }
     }
+
+    pub fn with_span(mut self, start: usize, end: usize) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    pub fn new_expected_error(expected: &[TokenType], found: &Token) -> Self {
+        let expected = expected.iter().map(|_type| format!("{:?}", _type)).collect::<Vec<String>>().join(", ");
+
+        Token::new_error(TokenType::SyntaxError, format!("expected one of [{expected}], found {:?}", found._type)).with_span(found.start, found.end)
+    }
 }
 
 pub fn parse_equation(tokens: Vec<&Token>) -> Vec<Token> {
@@ -595,10 +637,11 @@ pub fn parse_equation(tokens: Vec<&Token>) -> Vec<Token> {
 
 pub fn generate_tokens(code: &str) -> Vec<Token> {
     let mut tokens: Vec<Token> = Vec::new();
-    let mut code = code.chars().peekable();
+    let code_len = code.len();
+    let mut code = code.char_indices().peekable();
 
-    fn check_next(code: &mut Peekable<Chars>, type1: TokenType, type2: TokenType, value: char) -> Token {
-        if let Some(&c) = code.peek() {
+    fn check_next(code: &mut Peekable<CharIndices>, type1: TokenType, type2: TokenType, value: char) -> Token {
+        if let Some(&(_, c)) = code.peek() {
             if c != value {
                 Token::new(type1)
             } else {
@@ -611,8 +654,10 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
     }
 
     let mut multiplier = 1.0;
+    let mut neg_start: Option<usize> = None;
 
-    while let Some(c) = code.next() {
+    while let Some((start, c)) = code.next() {
+        let start = neg_start.take().unwrap_or(start);
         let token = match c {
             '(' => Token::new(TokenType::LeftParen),
             ')' => Token::new(TokenType::RightParen),
@@ -625,9 +670,10 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
             ';' => Token::new(TokenType::Semicolon),
             '+' => check_next(&mut code, TokenType::Plus, TokenType::PlusEqual, '='),
             '-' => {
-                if let Some(&c) = code.peek() {
+                if let Some(&(_, c)) = code.peek() {
                     if '9' >= c && c >= '0' {
                         multiplier = -1.0;
+                        neg_start = Some(start);
                         continue;
                     }
                 }
@@ -641,7 +687,7 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
             '>' => check_next(&mut code, TokenType::Greater, TokenType::GreaterEqual, '='),
             '<' => check_next(&mut code, TokenType::Less, TokenType::LessEqual, '='),
             '#' => {
-                while let Some(c) = code.next() {
+                while let Some((_, c)) = code.next() {
                     if c == '\n' {
                         break;
                     }
@@ -654,7 +700,7 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
                 let mut fract = 0.0;
                 let mut divider = 0;
 
-                while let Some(&c) = code.peek() {
+                while let Some(&(_, c)) = code.peek() {
                     if divider == 0 && '9' >= c && c >= '0' {
                         num = num * 10.0 + -('0' as i32 as f64 - c as i32 as f64);
                     } else if c == '.' {
@@ -681,7 +727,7 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
                 let mut string = String::new();
                 string.push(c);
 
-                while let Some(&c) = code.peek() {
+                while let Some(&(_, c)) = code.peek() {
                     if c.is_alphanumeric() || c == '_' {
                         string.push(c);
                         code.next();
@@ -708,21 +754,22 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
                 let mut string = String::new();
                 let mut closed = false;
 
-                while let Some(&c) = code.peek() {
+                while let Some(&(_, c)) = code.peek() {
                     if c == '"' {
                         code.next();
                         closed = true;
                         break
                     } else {
                         if c == '\n' {
-                            tokens.push(Token::new_error(TokenType::SyntaxError, "String not closed".to_string()));
+                            let end = code.peek().map(|&(i, _)| i).unwrap_or(code_len);
+                            tokens.push(Token::new_error(TokenType::SyntaxError, "String not closed".to_string()).with_span(start, end));
                             return tokens;
                         }
 
                         if c == '\\' {
                             code.next();
 
-                            if let Some(&c) = code.peek() {
+                            if let Some(&(_, c)) = code.peek() {
                                 match c {
                                     'n' => string.push('\n'),
                                     't' => string.push('\t'),
@@ -741,21 +788,25 @@ pub fn generate_tokens(code: &str) -> Vec<Token> {
                 }
 
                 if !closed {
-                    tokens.push(Token::new_error(TokenType::SyntaxError, "String not closed".to_string()));
+                    let end = code.peek().map(|&(i, _)| i).unwrap_or(code_len);
+                    tokens.push(Token::new_error(TokenType::SyntaxError, "String not closed".to_string()).with_span(start, end));
                     return tokens;
                 }
 
                 Token::new_string(string)
             },
             '&' => {
-                tokens.push(Token::new_var("&".to_string()));
+                let end = code.peek().map(|&(i, _)| i).unwrap_or(code_len);
+                tokens.push(Token::new_var("&".to_string()).with_span(start, end));
                 Token::new(TokenType::Dot)
             },
             ' ' | '\n' | '\t' | '\r' => continue,
             _ => Token::new_error(TokenType::Error, format!("{} is not a valid character", c))
         };
 
-        tokens.push(token);
+        let end = code.peek().map(|&(i, _)| i).unwrap_or(code_len);
+
+        tokens.push(token.with_span(start, end));
     }
 
     tokens
@@ -17,7 +17,7 @@ limitations under the License.
 #![warn(clippy::pedantic)]
 
 use crate::utils::*;
-use pyo3::{ffi::PyModule_GetFilename, prelude::*, types::{PyBool, PyDict, PyString, PyTuple}, PyErrArguments};
+use pyo3::{ffi::PyModule_GetFilename, prelude::*, exceptions::PyValueError, types::{PyBool, PyDict, PyString, PyTuple}, PyErrArguments};
 use std::ops::Bound;
 
 mod lexer;
@@ -25,6 +25,9 @@ mod parser;
 mod interpreter;
 mod builtins;
 mod utils;
+mod analyzer;
+
+const BYTECODE_VERSION: u8 = 2;
 
 #[pyfunction]
 fn generate_tokens(py: Python, code: String) -> PyResult<Vec<&PyDict>> {
@@ -40,26 +43,24 @@ fn generate_tokens(py: Python, code: String) -> PyResult<Vec<&PyDict>> {
 }
 
 #[pyfunction]
-fn generate_ast<'a>(py: Python<'a>, tokens: Vec<&PyDict>) -> PyResult<Vec<&'a PyDict>> {
+fn generate_ast<'a>(py: Python<'a>, tokens: Vec<&PyDict>) -> PyResult<(Vec<&'a PyDict>, Vec<&'a PyDict>)> {
     let mut rust_tokens: Vec<lexer::Token> = Vec::new();
 
     for token in tokens {
         rust_tokens.push(convert_to_token(py, token));
     }
 
-    let ast = parser::generate_ast(rust_tokens.iter().collect());
+    let (ast, errors) = parser::generate_ast(rust_tokens.iter().collect());
 
-    Ok(convert_ast(py, ast))
+    Ok((convert_ast(py, ast), errors.into_iter().map(|error| convert_token(py, error)).collect()))
 }
 
-#[pyfunction]
-fn execute_ast<'a>(py: Python<'a>, ast: Vec<&PyDict>, variables: Vec<&PyDict>, functions: Vec<&PyDict>, modules: &PyDict, debug: &PyBool) -> PyResult<&'a PyAny> {
-    let rust_ast = convert_to_ast(py, ast);
-
+fn build_scope(py: Python, variables: Vec<&PyDict>, functions: Vec<&PyDict>, debug: &PyBool, max_steps: Option<u64>) -> interpreter::Scope {
     let mut scope = utils::get_scope(py, variables);
     let mut builtins = builtins::get_builtins();
 
     scope.functions.append(&mut builtins);
+    scope.fuel = interpreter::Fuel::new(max_steps);
 
     if debug.is_true() {
         scope.functions.push(interpreter::Function::new_builtin("print"));
@@ -73,8 +74,13 @@ fn execute_ast<'a>(py: Python<'a>, ast: Vec<&PyDict>, variables: Vec<&PyDict>, f
         scope.push_pyfunc(&name, func);
     }
 
+    scope
+}
+
+fn run_ast<'a>(py: Python<'a>, rust_ast: Vec<parser::AST>, mut scope: interpreter::Scope, modules: &PyDict, timeout_ms: Option<u64>) -> PyResult<&'a PyAny> {
     let mut module_asts: Vec<Vec<parser::AST>> = Vec::new();
     let mut module = String::new();
+    let mut module_span = (0, 0);
 
     for ast in rust_ast.to_owned() {
         if ast.token._type == lexer::TokenType::Import {
@@ -83,9 +89,11 @@ fn execute_ast<'a>(py: Python<'a>, ast: Vec<&PyDict>, variables: Vec<&PyDict>, f
                     if result {
                         module_asts.push(convert_to_ast(py, modules.get_item(&ast.token.value).unwrap().extract().unwrap()));
                     } else {
+                        module_span = (ast.token.start, ast.token.end);
                         module = ast.token.value;
                     }
                 } else {
+                    module_span = (ast.token.start, ast.token.end);
                     module = ast.token.value;
                 }
             }
@@ -94,19 +102,98 @@ fn execute_ast<'a>(py: Python<'a>, ast: Vec<&PyDict>, variables: Vec<&PyDict>, f
 
     pyo3_asyncio::tokio::future_into_py(py, async move {
         if !module.is_empty() {
-            return Ok(Python::with_gil(|py| (convert_token(py, lexer::Token::new_error(lexer::TokenType::ModuleNotfound, format!("no module named {}", module))).as_ref().to_object(py), PyDict::new(py).to_object(py))))
-        }
+            let error = lexer::Token::new_error(lexer::TokenType::ModuleNotfound, format!("no module named {}", module)).with_span(module_span.0, module_span.1);
 
-        for ast in module_asts {
-            interpreter::execute_ast(ast, &mut scope, None, 0).await;
+            return Ok(Python::with_gil(|py| (convert_token(py, error).as_ref().to_object(py), PyDict::new(py).to_object(py))))
         }
 
-        let result = interpreter::execute_ast(rust_ast, &mut scope, None, 0).await;
+        // The interpreter's hot loop yields on every statement it executes, so a
+        // timeout below preempts even non-recursive scripts instead of only
+        // catching recursion that overruns the step budget.
+        let run = async {
+            for ast in module_asts {
+                interpreter::execute_ast(ast, &mut scope, None, 0).await;
+            }
+
+            interpreter::execute_ast(rust_ast, &mut scope, None, 0).await
+        };
+
+        let result = match timeout_ms {
+            Some(timeout_ms) => match tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), run).await {
+                Ok(result) => result,
+                Err(_) => lexer::Token::new_error(lexer::TokenType::StepLimitExceeded, format!("execution timed out after {timeout_ms}ms"))
+            },
+            None => run.await
+        };
 
         Ok(Python::with_gil(|py| (convert_token(py, result).as_ref().to_object(py).clone(), walk_scope(py, scope).to_object(py).clone())))
     })
 }
 
+#[pyfunction]
+#[pyo3(signature = (ast, variables, functions, modules, debug, timeout_ms = None, max_steps = None))]
+fn execute_ast<'a>(py: Python<'a>, ast: Vec<&PyDict>, variables: Vec<&PyDict>, functions: Vec<&PyDict>, modules: &PyDict, debug: &PyBool, timeout_ms: Option<u64>, max_steps: Option<u64>) -> PyResult<&'a PyAny> {
+    let rust_ast = convert_to_ast(py, ast);
+    let scope = build_scope(py, variables, functions, debug, max_steps);
+
+    run_ast(py, rust_ast, scope, modules, timeout_ms)
+}
+
+#[pyfunction]
+fn compile(code: String) -> PyResult<Vec<u8>> {
+    let tokens = lexer::generate_tokens(&code);
+    let (ast, errors) = parser::generate_ast(tokens.iter().collect());
+
+    if let Some(error) = errors.into_iter().next() {
+        return Err(PyValueError::new_err(error.value));
+    }
+
+    bincode::serialize(&(BYTECODE_VERSION, ast)).map_err(|error| PyValueError::new_err(error.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(signature = (bytes, variables, functions, modules, debug, timeout_ms = None, max_steps = None))]
+fn execute_compiled<'a>(py: Python<'a>, bytes: Vec<u8>, variables: Vec<&PyDict>, functions: Vec<&PyDict>, modules: &PyDict, debug: &PyBool, timeout_ms: Option<u64>, max_steps: Option<u64>) -> PyResult<&'a PyAny> {
+    let (version, rust_ast): (u8, Vec<parser::AST>) = bincode::deserialize(&bytes).map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    if version != BYTECODE_VERSION {
+        return Err(PyValueError::new_err(format!("compiled bytecode has format version {version}, expected {BYTECODE_VERSION}")));
+    }
+
+    // The bytecode already holds the parsed AST, so replaying a call skips both
+    // lexing and parsing, not just the Python-dict round-trip.
+    let scope = build_scope(py, variables, functions, debug, max_steps);
+
+    run_ast(py, rust_ast, scope, modules, timeout_ms)
+}
+
+#[pyfunction]
+fn analyze_ast<'a>(py: Python<'a>, ast: Vec<&PyDict>, variables: Vec<&PyDict>, functions: Vec<&PyDict>, modules: &PyDict) -> PyResult<Vec<&'a PyDict>> {
+    let rust_ast = convert_to_ast(py, ast);
+
+    let mut names: std::collections::HashMap<String, Option<usize>> = std::collections::HashMap::new();
+
+    for function in builtins::get_builtins() {
+        names.insert(function.name, None);
+    }
+
+    for variable in variables {
+        names.insert(variable.get_item("name").unwrap().extract::<String>().unwrap(), None);
+    }
+
+    for function in functions {
+        names.insert(function.get_item("name").unwrap().extract::<String>().unwrap(), None);
+    }
+
+    for key in modules.keys() {
+        names.insert(key.extract::<String>().unwrap(), None);
+    }
+
+    let errors = analyzer::analyze(&rust_ast, names);
+
+    Ok(errors.into_iter().map(|token| convert_token(py, token)).collect())
+}
+
 #[pyfunction]
 fn parse_equation<'a>(py: Python<'a>, tokens: Vec<&PyDict>) -> PyResult<Vec<&'a PyDict>> {
     let mut rust_tokens: Vec<lexer::Token> = Vec::new();
@@ -126,6 +213,34 @@ fn parse_equation<'a>(py: Python<'a>, tokens: Vec<&PyDict>) -> PyResult<Vec<&'a
     Ok(py_tokens)
 }
 
+#[pyfunction]
+#[pyo3(signature = (code, token, color = false))]
+fn render_diagnostic(py: Python, code: String, token: &PyDict, color: bool) -> PyResult<String> {
+    let token = convert_to_token(py, token);
+
+    if token.start >= token.end {
+        return Ok(token.value);
+    }
+
+    let (line_start, line_number) = code[..token.start].char_indices().rev()
+        .find(|&(_, c)| c == '\n')
+        .map(|(i, _)| (i + 1, code[..i].matches('\n').count() + 2))
+        .unwrap_or((0, 1));
+
+    let line_end = code[token.start..].find('\n').map(|i| token.start + i).unwrap_or(code.len());
+    let line = &code[line_start..line_end];
+
+    let column = token.start - line_start;
+    let span_len = (token.end - token.start).max(1).min(line.len().saturating_sub(column).max(1));
+
+    let gutter = format!("{line_number} | ");
+    let underline = format!("{}{}", " ".repeat(column), "^".repeat(span_len));
+
+    let (prefix, suffix) = if color { ("\x1b[31m", "\x1b[0m") } else { ("", "") };
+
+    Ok(format!("{gutter}{line}\n{}{prefix}{underline} {}{suffix}", " ".repeat(gutter.len()), token.value))
+}
+
 #[pyfunction]
 #[pyo3(signature = (*args, **kwargs))]
 fn format_string<'a>(py: Python<'a>, args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<&'a PyAny> {
@@ -148,7 +263,11 @@ fn femscript(_py: Python, module: &PyModule) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(generate_tokens, module)?)?;
     module.add_function(wrap_pyfunction!(generate_ast, module)?)?;
     module.add_function(wrap_pyfunction!(execute_ast, module)?)?;
+    module.add_function(wrap_pyfunction!(compile, module)?)?;
+    module.add_function(wrap_pyfunction!(execute_compiled, module)?)?;
+    module.add_function(wrap_pyfunction!(analyze_ast, module)?)?;
     module.add_function(wrap_pyfunction!(parse_equation, module)?)?;
+    module.add_function(wrap_pyfunction!(render_diagnostic, module)?)?;
     module.add_function(wrap_pyfunction!(format_string, module)?)?;
 
     Ok(())
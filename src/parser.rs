@@ -0,0 +1,485 @@
+/*
+Copyright 2022-2025 czubix
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::lexer::{Token, TokenType};
+use serde::{Deserialize, Serialize};
+
+/// AST node kinds reuse `TokenType` instead of duplicating the variant list:
+/// a node's kind is almost always the token that introduced it (`Func`,
+/// `Import`, `If`, an operator, ...), so a separate enum would just be a
+/// shadow copy that `ASTType::from_str`/`{:?}` round-tripping in `utils.rs`
+/// would have to keep in sync by hand.
+pub type ASTType = TokenType;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AST {
+    pub _type: ASTType,
+    pub token: Token,
+    pub children: Vec<AST>
+}
+
+const ASSIGN_OPS: [TokenType; 6] = [
+    TokenType::Equal, TokenType::PlusEqual, TokenType::MinusEqual,
+    TokenType::MultiplyEqual, TokenType::DivideEqual, TokenType::ModuloEqual
+];
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    errors: Vec<Token>
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0, errors: Vec::new() }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_type(&self) -> TokenType {
+        self.peek().map(|token| token._type.clone()).unwrap_or(TokenType::Unknown)
+    }
+
+    fn peek_next_type(&self) -> TokenType {
+        self.tokens.get(self.pos + 1).map(|token| token._type.clone()).unwrap_or(TokenType::Unknown)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+
+        if token.is_some() {
+            self.pos += 1;
+        }
+
+        token
+    }
+
+    fn check(&self, _type: &TokenType) -> bool {
+        self.peek().map(|token| &token._type == _type).unwrap_or(false)
+    }
+
+    fn eat(&mut self, _type: TokenType) -> Option<Token> {
+        if self.check(&_type) {
+            self.advance()
+        } else {
+            None
+        }
+    }
+
+    fn expect(&mut self, expected: &[TokenType]) -> Option<Token> {
+        for _type in expected {
+            if self.check(_type) {
+                return self.advance();
+            }
+        }
+
+        let found = self.peek().cloned().unwrap_or_else(|| {
+            let end = self.tokens.last().map(|token| token.end).unwrap_or(0);
+            Token::new(TokenType::Unknown).with_span(end, end)
+        });
+
+        self.record_error(Token::new_expected_error(expected, &found));
+
+        None
+    }
+
+    fn record_error(&mut self, error: Token) {
+        // Resynchronizing after the first error in a statement tends to produce
+        // a cascade of follow-on errors at the same position; only the first
+        // error recorded at a given start offset is kept.
+        if self.errors.last().map(|last| last.start == error.start).unwrap_or(false) {
+            return;
+        }
+
+        self.errors.push(error);
+    }
+
+    fn synchronize(&mut self) {
+        if self.advance().is_none() {
+            return;
+        }
+
+        while let Some(token) = self.peek() {
+            match token._type {
+                TokenType::Semicolon => {
+                    self.advance();
+                    return;
+                },
+                TokenType::RightBrace => return,
+                _ => { self.advance(); }
+            }
+        }
+    }
+
+    fn parse_program(&mut self) -> Vec<AST> {
+        let mut statements = Vec::new();
+
+        while !self.at_end() {
+            match self.parse_statement() {
+                Some(statement) => statements.push(statement),
+                None => self.synchronize()
+            }
+        }
+
+        statements
+    }
+
+    fn parse_block(&mut self) -> Option<Vec<AST>> {
+        self.expect(&[TokenType::LeftBrace])?;
+
+        let mut statements = Vec::new();
+
+        while !self.check(&TokenType::RightBrace) && !self.at_end() {
+            match self.parse_statement() {
+                Some(statement) => statements.push(statement),
+                None => self.synchronize()
+            }
+        }
+
+        self.expect(&[TokenType::RightBrace])?;
+
+        Some(statements)
+    }
+
+    fn parse_statement(&mut self) -> Option<AST> {
+        match self.peek_type() {
+            TokenType::Comment => {
+                self.advance();
+                self.parse_statement()
+            },
+            TokenType::Import => self.parse_import(),
+            TokenType::Func => self.parse_func(),
+            TokenType::If => self.parse_if(),
+            TokenType::Return => self.parse_return(),
+            _ => self.parse_simple_statement()
+        }
+    }
+
+    fn parse_import(&mut self) -> Option<AST> {
+        let token = self.advance()?;
+
+        let mut children = Vec::new();
+
+        loop {
+            let name = self.expect(&[TokenType::Var])?;
+            children.push(AST { _type: TokenType::Var, token: name, children: Vec::new() });
+
+            if self.eat(TokenType::Comma).is_none() {
+                break;
+            }
+        }
+
+        self.expect(&[TokenType::Semicolon])?;
+
+        Some(AST { _type: TokenType::Import, token, children })
+    }
+
+    fn parse_func(&mut self) -> Option<AST> {
+        self.advance()?;
+
+        let name = self.expect(&[TokenType::Var])?;
+
+        self.expect(&[TokenType::LeftParen])?;
+
+        let mut params = Vec::new();
+
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                let param = self.expect(&[TokenType::Var])?;
+                params.push(AST { _type: TokenType::Var, token: param, children: Vec::new() });
+
+                if self.eat(TokenType::Comma).is_none() {
+                    break;
+                }
+            }
+        }
+
+        self.expect(&[TokenType::RightParen])?;
+
+        let body = self.parse_block()?;
+
+        let mut children = vec![AST { _type: TokenType::List, token: Token::new(TokenType::List), children: params }];
+        children.extend(body);
+
+        let token = Token { _type: TokenType::Func, ..name };
+
+        Some(AST { _type: TokenType::Func, token, children })
+    }
+
+    fn parse_if(&mut self) -> Option<AST> {
+        let token = self.advance()?;
+
+        let cond = self.parse_expr()?;
+        let then_body = self.parse_block()?;
+        let then_node = AST { _type: TokenType::Scope, token: Token::new(TokenType::Scope), children: then_body };
+
+        let mut children = vec![cond, then_node];
+
+        if self.eat(TokenType::Else).is_some() {
+            if self.check(&TokenType::If) {
+                let else_if = self.parse_if()?;
+                children.push(AST { _type: TokenType::Scope, token: Token::new(TokenType::Scope), children: vec![else_if] });
+            } else {
+                let else_body = self.parse_block()?;
+                children.push(AST { _type: TokenType::Scope, token: Token::new(TokenType::Scope), children: else_body });
+            }
+        }
+
+        Some(AST { _type: TokenType::If, token, children })
+    }
+
+    fn parse_return(&mut self) -> Option<AST> {
+        let token = self.advance()?;
+
+        let mut children = Vec::new();
+
+        if !self.check(&TokenType::Semicolon) {
+            children.push(self.parse_expr()?);
+        }
+
+        self.expect(&[TokenType::Semicolon])?;
+
+        Some(AST { _type: TokenType::Return, token, children })
+    }
+
+    fn parse_simple_statement(&mut self) -> Option<AST> {
+        if self.check(&TokenType::Var) && ASSIGN_OPS.contains(&self.peek_next_type()) {
+            let name = self.advance()?;
+            let op = self.advance()?;
+            let value = self.parse_expr()?;
+
+            self.expect(&[TokenType::Semicolon])?;
+
+            let token = Token { _type: op._type, ..name };
+
+            return Some(AST { _type: token._type.clone(), token, children: vec![value] });
+        }
+
+        let expr = self.parse_expr()?;
+
+        self.expect(&[TokenType::Semicolon])?;
+
+        Some(expr)
+    }
+
+    fn parse_expr(&mut self) -> Option<AST> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Option<AST> {
+        let mut left = self.parse_and()?;
+
+        while self.check(&TokenType::Or) {
+            let token = self.advance()?;
+            let right = self.parse_and()?;
+            left = AST { _type: TokenType::Or, token, children: vec![left, right] };
+        }
+
+        Some(left)
+    }
+
+    fn parse_and(&mut self) -> Option<AST> {
+        let mut left = self.parse_equality()?;
+
+        while self.check(&TokenType::And) {
+            let token = self.advance()?;
+            let right = self.parse_equality()?;
+            left = AST { _type: TokenType::And, token, children: vec![left, right] };
+        }
+
+        Some(left)
+    }
+
+    fn parse_equality(&mut self) -> Option<AST> {
+        let mut left = self.parse_comparison()?;
+
+        while matches!(self.peek_type(), TokenType::EqualTo | TokenType::NotEqual) {
+            let token = self.advance()?;
+            let _type = token._type.clone();
+            let right = self.parse_comparison()?;
+            left = AST { _type, token, children: vec![left, right] };
+        }
+
+        Some(left)
+    }
+
+    fn parse_comparison(&mut self) -> Option<AST> {
+        let mut left = self.parse_additive()?;
+
+        while matches!(self.peek_type(), TokenType::Greater | TokenType::Less | TokenType::GreaterEqual | TokenType::LessEqual) {
+            let token = self.advance()?;
+            let _type = token._type.clone();
+            let right = self.parse_additive()?;
+            left = AST { _type, token, children: vec![left, right] };
+        }
+
+        Some(left)
+    }
+
+    fn parse_additive(&mut self) -> Option<AST> {
+        let mut left = self.parse_multiplicative()?;
+
+        while matches!(self.peek_type(), TokenType::Plus | TokenType::Minus) {
+            let token = self.advance()?;
+            let _type = token._type.clone();
+            let right = self.parse_multiplicative()?;
+            left = AST { _type, token, children: vec![left, right] };
+        }
+
+        Some(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Option<AST> {
+        let mut left = self.parse_unary()?;
+
+        while matches!(self.peek_type(), TokenType::Multiply | TokenType::Divide | TokenType::Modulo) {
+            let token = self.advance()?;
+            let _type = token._type.clone();
+            let right = self.parse_unary()?;
+            left = AST { _type, token, children: vec![left, right] };
+        }
+
+        Some(left)
+    }
+
+    fn parse_unary(&mut self) -> Option<AST> {
+        if matches!(self.peek_type(), TokenType::Not | TokenType::Minus) {
+            let token = self.advance()?;
+            let _type = token._type.clone();
+            let operand = self.parse_unary()?;
+
+            return Some(AST { _type, token, children: vec![operand] });
+        }
+
+        self.parse_postfix()
+    }
+
+    fn parse_postfix(&mut self) -> Option<AST> {
+        let mut node = self.parse_primary()?;
+
+        while self.check(&TokenType::Dot) {
+            self.advance();
+
+            let name = self.expect(&[TokenType::Var])?;
+
+            self.expect(&[TokenType::LeftParen])?;
+            let args = self.parse_args()?;
+
+            let mut children = vec![node];
+            children.extend(args);
+
+            node = AST { _type: TokenType::Dot, token: name, children };
+        }
+
+        Some(node)
+    }
+
+    fn parse_args(&mut self) -> Option<Vec<AST>> {
+        let mut args = Vec::new();
+
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                args.push(self.parse_expr()?);
+
+                if self.eat(TokenType::Comma).is_none() {
+                    break;
+                }
+            }
+        }
+
+        self.expect(&[TokenType::RightParen])?;
+
+        Some(args)
+    }
+
+    fn parse_primary(&mut self) -> Option<AST> {
+        match self.peek_type() {
+            TokenType::Int | TokenType::Str | TokenType::Bool | TokenType::None | TokenType::Bytes => {
+                let token = self.advance()?;
+                let _type = token._type.clone();
+                Some(AST { _type, token, children: Vec::new() })
+            },
+            TokenType::LeftBracket => {
+                let token = self.advance()?;
+                let mut items = Vec::new();
+
+                if !self.check(&TokenType::RightBracket) {
+                    loop {
+                        items.push(self.parse_expr()?);
+
+                        if self.eat(TokenType::Comma).is_none() {
+                            break;
+                        }
+                    }
+                }
+
+                self.expect(&[TokenType::RightBracket])?;
+
+                Some(AST { _type: TokenType::List, token, children: items })
+            },
+            TokenType::LeftParen => {
+                self.advance();
+                let expr = self.parse_expr()?;
+                self.expect(&[TokenType::RightParen])?;
+                Some(expr)
+            },
+            TokenType::Var => {
+                let name = self.advance()?;
+
+                if self.check(&TokenType::LeftParen) {
+                    self.advance();
+                    let args = self.parse_args()?;
+                    Some(AST { _type: TokenType::LeftParen, token: name, children: args })
+                } else {
+                    Some(AST { _type: TokenType::Var, token: name, children: Vec::new() })
+                }
+            },
+            _ => {
+                let found = self.peek().cloned().unwrap_or_else(|| Token::new(TokenType::Unknown));
+
+                self.record_error(Token::new_expected_error(
+                    &[
+                        TokenType::Int, TokenType::Str, TokenType::Bool, TokenType::None, TokenType::Bytes,
+                        TokenType::Var, TokenType::LeftParen, TokenType::LeftBracket, TokenType::Not, TokenType::Minus
+                    ],
+                    &found
+                ));
+
+                None
+            }
+        }
+    }
+}
+
+/// Parses a flat token stream into a program (a list of statement nodes).
+///
+/// Unlike a fail-fast parser, this keeps going past a malformed statement: on
+/// a parse failure it records a `SyntaxError` token carrying the expected set
+/// and resynchronizes at the next `;`/`}` before continuing, so a single typo
+/// doesn't hide every other mistake in the script from the caller.
+pub fn generate_ast(tokens: Vec<&Token>) -> (Vec<AST>, Vec<Token>) {
+    let tokens: Vec<Token> = tokens.into_iter().cloned().collect();
+    let mut parser = Parser::new(tokens);
+    let ast = parser.parse_program();
+
+    (ast, parser.errors)
+}
@@ -31,6 +31,8 @@ pub fn convert_token(py: Python, token: Token) -> &PyDict {
     py_token.set_item("number", token.number).unwrap();
     py_token.set_item("list", PyList::new(py, list)).unwrap();
     py_token.set_item("bytes", token.bytes).unwrap();
+    py_token.set_item("start", token.start).unwrap();
+    py_token.set_item("end", token.end).unwrap();
 
     if let Some(scope) = token.scope {
         py_token.set_item("scope", walk_scope(py, scope)).unwrap();
@@ -70,7 +72,9 @@ pub fn convert_to_token(py: Python, token: &PyDict) -> Token {
         } else {
             None
         },
-        rustobject: None
+        rustobject: None,
+        start: token.get_item("start").and_then(|start| start.extract::<usize>().ok()).unwrap_or(0),
+        end: token.get_item("end").and_then(|end| end.extract::<usize>().ok()).unwrap_or(0)
     }
 }
 